@@ -3,19 +3,27 @@ use clap::Parser;
 use image::RgbaImage;
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
+use ruffle_core::backend::log::LogBackend;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::PlayerBuilder;
-use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
-use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
+use ruffle_core::{Color, PlayerBuilder};
+use ruffle_render::quality::StageQuality;
+use ruffle_render_wgpu::backend::{
+    request_adapter_and_device, request_device_for_adapter, WgpuRenderBackend,
+};
+use ruffle_render_wgpu::clap::{
+    describe_adapters, AdapterSelector, GraphicsBackend, PowerPreference,
+};
 use ruffle_render_wgpu::descriptors::Descriptors;
 use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::wgpu;
-use std::fs::create_dir_all;
+use std::fs::{create_dir_all, File};
 use std::io::{self, Write};
-use std::panic::catch_unwind;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
 use walkdir::{DirEntry, WalkDir};
 
 #[derive(Parser, Debug, Copy, Clone)]
@@ -33,12 +41,50 @@ struct SizeOpt {
     height: Option<u32>,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+enum AlphaMode {
+    Straight,
+    Premultiplied,
+}
+
+/// What the movie is rendered on top of
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Background {
+    /// The movie's own background color, from its SetBackgroundColor tag
+    Swf,
+    /// Nothing, leaving the parts of the stage that the movie doesn't cover transparent
+    Transparent,
+    /// A fixed color
+    Color(Color),
+}
+
+impl FromStr for Background {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "swf" => Ok(Background::Swf),
+            "transparent" => Ok(Background::Transparent),
+            color => {
+                let hex = color.strip_prefix('#').unwrap_or(color);
+                if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(format!(
+                        "expected swf, transparent or #RRGGBB, found {color:?}"
+                    ));
+                }
+                let rgb = u32::from_str_radix(hex, 16).map_err(|e| e.to_string())?;
+                Ok(Background::Color(Color::from_rgb(rgb, 255)))
+            }
+        }
+    }
+}
+
 #[derive(Parser, Debug)]
 #[clap(name = "Ruffle Exporter", author, version)]
 struct Opt {
     /// The file or directory of files to export frames from
-    #[clap(name = "swf")]
-    swf: PathBuf,
+    #[clap(name = "swf", required_unless_present = "list_adapters")]
+    swf: Option<PathBuf>,
 
     /// The file or directory (if multiple frames/files) to store the capture in.
     /// The default value will either be:
@@ -48,6 +94,11 @@ struct Opt {
     #[clap(name = "output")]
     output_path: Option<PathBuf>,
 
+    /// A "flashvars" parameter to provide to the movie.
+    /// This can be repeated multiple times, for example -Pkey=value -Pfoo=bar.
+    #[clap(short = 'P', long = "parameter", action = clap::ArgAction::Append)]
+    parameters: Vec<String>,
+
     /// Number of frames to capture per file
     #[clap(short = 'f', long = "frames", default_value = "1")]
     frames: u32,
@@ -63,6 +114,23 @@ struct Opt {
     #[clap(flatten)]
     size: SizeOpt,
 
+    /// Default quality of the movie.
+    #[clap(long, short, default_value = "high")]
+    quality: StageQuality,
+
+    /// Set and lock the player's frame rate, overriding the movie's frame rate.
+    #[clap(long)]
+    frame_rate: Option<f64>,
+
+    /// Whether the color channels of the saved images are straight or premultiplied by alpha
+    #[clap(long, default_value = "straight")]
+    alpha: AlphaMode,
+
+    /// Background to render the movie on: "swf" for the movie's own background color,
+    /// "transparent", or a color given as #RRGGBB.
+    #[clap(long, default_value = "swf")]
+    background: Background,
+
     /// Type of graphics backend to use. Not all options may be supported by your current system.
     /// Default will attempt to pick the most supported graphics backend.
     #[clap(long, short, default_value = "default")]
@@ -73,6 +141,20 @@ struct Opt {
     #[clap(long, short, default_value = "high")]
     power: PowerPreference,
 
+    /// Graphics adapter to use, given either as its index from `--list-adapters` or as part of its name.
+    /// Default will pick an adapter based on the power preference.
+    /// Choosing an adapter disables falling back to other graphics backends.
+    #[clap(long)]
+    adapter: Option<AdapterSelector>,
+
+    /// List the graphics adapters available for the chosen graphics backend, then exit
+    #[clap(long, action)]
+    list_adapters: bool,
+
+    /// Fail if the chosen graphics backend can't be used, instead of falling back to other backends
+    #[clap(long, action)]
+    strict_graphics: bool,
+
     /// Location to store a wgpu trace output
     #[clap(long)]
     #[cfg(feature = "render_trace")]
@@ -81,22 +163,80 @@ struct Opt {
     /// Skip unsupported movie types (currently AVM 2)
     #[clap(long, action)]
     skip_unsupported: bool,
+
+    /// Stop at the first file that fails to export, instead of exporting the rest of the directory
+    #[clap(long, action)]
+    fail_fast: bool,
+
+    /// Number of files to export at the same time when given a directory.
+    /// Default will use one for each CPU.
+    #[clap(long, short)]
+    jobs: Option<usize>,
+
+    /// File to write trace() output from the movies to, or "-" to write it to stderr.
+    /// Each line is prefixed with the name of the movie it came from.
+    #[clap(long)]
+    trace_output: Option<PathBuf>,
 }
 
-/// Captures a screenshot. The resulting image uses straight alpha
+impl Opt {
+    fn parameters(&self) -> impl '_ + Iterator<Item = (String, String)> {
+        self.parameters.iter().map(|parameter| {
+            let mut split = parameter.splitn(2, '=');
+            if let (Some(key), Some(value)) = (split.next(), split.next()) {
+                (key.to_owned(), value.to_owned())
+            } else {
+                (parameter.clone(), "".to_string())
+            }
+        })
+    }
+}
+
+/// Destination for trace() output, shared by every movie being exported
+type TraceOutput = Arc<Mutex<Box<dyn Write + Send>>>;
+
+/// Log backend that writes trace() output, prefixed with the name of the movie it came from
+struct TraceLogBackend {
+    movie_name: String,
+    output: TraceOutput,
+}
+
+impl LogBackend for TraceLogBackend {
+    fn avm_trace(&self, message: &str) {
+        // A movie that panicked while writing must not stop the others from tracing.
+        let mut output = self.output.lock().unwrap_or_else(PoisonError::into_inner);
+        for line in message.split('\n') {
+            let _ = writeln!(output, "[{}] {line}", self.movie_name);
+        }
+    }
+}
+
+fn open_trace_output(path: &Path) -> Result<TraceOutput> {
+    let output: Box<dyn Write + Send> = if path == Path::new("-") {
+        Box::new(io::stderr())
+    } else {
+        Box::new(File::create(path)?)
+    };
+    Ok(Arc::new(Mutex::new(output)))
+}
+
+/// Captures a screenshot. The resulting image uses the alpha format chosen with `--alpha`.
+/// Returns `None` if the movie is unsupported and `--skip-unsupported` is set.
+/// `movie_name` prefixes each line the movie writes to the trace output.
 fn take_screenshot(
     descriptors: Arc<Descriptors>,
     swf_path: &Path,
-    frames: u32,
-    skipframes: u32,
+    movie_name: &str,
     progress: &Option<ProgressBar>,
-    size: SizeOpt,
-    skip_unsupported: bool,
-) -> Result<Vec<RgbaImage>> {
-    let movie = SwfMovie::from_path(swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
-
-    if movie.is_action_script_3() && skip_unsupported {
-        return Err(anyhow!("Skipping unsupported movie"));
+    opt: &Opt,
+    trace_output: Option<&TraceOutput>,
+) -> Result<Option<Vec<RgbaImage>>> {
+    let size = opt.size;
+    let mut movie = SwfMovie::from_path(swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
+    movie.append_parameters(opt.parameters());
+
+    if movie.is_action_script_3() && opt.skip_unsupported {
+        return Ok(None);
     }
 
     let width = size
@@ -113,16 +253,29 @@ fn take_screenshot(
 
     let target = TextureTarget::new(&descriptors.device, (width, height))
         .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
+    let mut builder = PlayerBuilder::new()
         .with_renderer(
             WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
         )
         .with_movie(movie)
         .with_viewport_dimensions(width, height, size.scale)
-        .build();
+        .with_quality(opt.quality)
+        .with_frame_rate(opt.frame_rate);
+    if let Some(output) = trace_output {
+        builder = builder.with_log(TraceLogBackend {
+            movie_name: movie_name.to_owned(),
+            output: output.clone(),
+        });
+    }
+    let player = builder.build();
+    match opt.background {
+        Background::Swf => {}
+        Background::Transparent => player.lock().unwrap().set_window_mode("transparent"),
+        Background::Color(color) => player.lock().unwrap().set_background_color(Some(color)),
+    }
 
     let mut result = Vec::new();
-    let totalframes = frames + skipframes;
+    let totalframes = opt.frames + opt.skipframes;
 
     for i in 0..totalframes {
         if let Some(progress) = &progress {
@@ -136,7 +289,7 @@ fn take_screenshot(
         player.lock().unwrap().preload(&mut ExecutionLimit::none());
 
         player.lock().unwrap().run_frame();
-        if i >= skipframes {
+        if i >= opt.skipframes {
             match catch_unwind(|| {
                 player.lock().unwrap().render();
                 let mut player = player.lock().unwrap();
@@ -144,7 +297,10 @@ fn take_screenshot(
                     .renderer_mut()
                     .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
                     .unwrap();
-                renderer.capture_frame()
+                match opt.alpha {
+                    AlphaMode::Straight => renderer.capture_frame(),
+                    AlphaMode::Premultiplied => renderer.capture_frame_premultiplied(),
+                }
             }) {
                 Ok(Some(image)) => result.push(image),
                 Ok(None) => return Err(anyhow!("Unable to capture frame {} of {:?}", i, swf_path)),
@@ -163,7 +319,7 @@ fn take_screenshot(
             progress.inc(1);
         }
     }
-    Ok(result)
+    Ok(Some(result))
 }
 
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
@@ -196,10 +352,15 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_single_swf(
+    descriptors: Arc<Descriptors>,
+    swf: &Path,
+    opt: &Opt,
+    trace_output: Option<&TraceOutput>,
+) -> Result<()> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
-        result.set_file_name(opt.swf.file_stem().unwrap());
+        result.set_file_name(swf.file_stem().unwrap());
         if opt.frames == 1 {
             result.set_extension("png");
         }
@@ -224,18 +385,12 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
         None
     };
 
-    let frames = take_screenshot(
-        descriptors,
-        &opt.swf,
-        opt.frames,
-        opt.skipframes,
-        &progress,
-        opt.size,
-        opt.skip_unsupported,
-    )?;
+    let movie_name = swf.file_stem().unwrap().to_string_lossy();
+    let frames = take_screenshot(descriptors, swf, &movie_name, &progress, opt, trace_output)?
+        .ok_or_else(|| anyhow!("Skipping unsupported movie"))?;
 
     if let Some(progress) = &progress {
-        progress.set_message(opt.swf.file_stem().unwrap().to_string_lossy().into_owned());
+        progress.set_message(swf.file_stem().unwrap().to_string_lossy().into_owned());
     }
 
     if frames.len() == 1 {
@@ -266,7 +421,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
         if !opt.silent {
             Some(format!(
                 "Saved first frame of {} to {}",
-                opt.swf.to_string_lossy(),
+                swf.to_string_lossy(),
                 output.to_string_lossy()
             ))
         } else {
@@ -276,7 +431,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
         Some(format!(
             "Saved first {} frames of {} to {}",
             frames.len(),
-            opt.swf.to_string_lossy(),
+            swf.to_string_lossy(),
             output.to_string_lossy()
         ))
     };
@@ -292,10 +447,63 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
     Ok(())
 }
 
+/// Captures one of the files found by `capture_multiple_swfs`, saving its frames in `output`
+/// under the same path relative to `root` as the file itself.
+/// Returns `false` if the file was skipped as unsupported.
 #[allow(clippy::branches_sharing_code)]
-fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_swf_in_directory(
+    descriptors: Arc<Descriptors>,
+    root: &Path,
+    file: &Path,
+    output: &Path,
+    progress: &Option<ProgressBar>,
+    opt: &Opt,
+    trace_output: Option<&TraceOutput>,
+) -> Result<bool> {
+    let mut relative_path = file.strip_prefix(root).unwrap_or(file).to_path_buf();
+    // Files with the same name can live in different subdirectories,
+    // so name the movie after its whole path within `root`.
+    let movie_name = relative_path
+        .with_extension("")
+        .to_string_lossy()
+        .replace('\\', "/");
+    let Some(frames) =
+        take_screenshot(descriptors, file, &movie_name, progress, opt, trace_output)?
+    else {
+        return Ok(false);
+    };
+
+    if frames.len() == 1 {
+        let mut destination: PathBuf = output.into();
+        relative_path.set_extension("png");
+        destination.push(relative_path);
+        if let Some(parent) = destination.parent() {
+            let _ = create_dir_all(parent);
+        }
+        frames.get(0).unwrap().save(&destination)?;
+    } else {
+        let mut parent: PathBuf = output.into();
+        relative_path.set_extension("");
+        parent.push(&relative_path);
+        let _ = create_dir_all(&parent);
+        for (frame, image) in frames.iter().enumerate() {
+            let mut destination = parent.clone();
+            destination.push(format!("{frame}.png"));
+            image.save(&destination)?;
+        }
+    }
+
+    Ok(true)
+}
+
+fn capture_multiple_swfs(
+    descriptors: Arc<Descriptors>,
+    swf: &Path,
+    opt: &Opt,
+    trace_output: Option<&TraceOutput>,
+) -> Result<()> {
     let output = opt.output_path.clone().unwrap();
-    let files = find_files(&opt.swf, !opt.silent);
+    let files = find_files(swf, !opt.silent);
 
     let progress = if !opt.silent {
         let progress = ProgressBar::new((files.len() as u64) * (opt.frames as u64));
@@ -311,6 +519,16 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
         None
     };
 
+    let succeeded = AtomicUsize::new(0);
+    let failed = Mutex::new(Vec::new());
+    let skipped = AtomicUsize::new(0);
+
+    if let Some(jobs) = opt.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()?;
+    }
+
     files.par_iter().try_for_each(|file| -> Result<()> {
         if let Some(progress) = &progress {
             progress.set_message(
@@ -321,59 +539,67 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
                     .into_owned(),
             );
         }
-        if let Ok(frames) = take_screenshot(
-            descriptors.clone(),
-            file.path(),
-            opt.frames,
-            opt.skipframes,
-            &progress,
-            opt.size,
-            opt.skip_unsupported,
-        ) {
-            let mut relative_path = file
-                .path()
-                .strip_prefix(&opt.swf)
-                .unwrap_or_else(|_| file.path())
-                .to_path_buf();
-
-            if frames.len() == 1 {
-                let mut destination: PathBuf = (&output).into();
-                relative_path.set_extension("png");
-                destination.push(relative_path);
-                if let Some(parent) = destination.parent() {
-                    let _ = create_dir_all(parent);
-                }
-                frames.get(0).unwrap().save(&destination)?;
-            } else {
-                let mut parent: PathBuf = (&output).into();
-                relative_path.set_extension("");
-                parent.push(&relative_path);
-                let _ = create_dir_all(&parent);
-                for (frame, image) in frames.iter().enumerate() {
-                    let mut destination = parent.clone();
-                    destination.push(format!("{frame}.png"));
-                    image.save(&destination)?;
+        // Each file gets its own player, so a panic while exporting one can't affect the others.
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            capture_swf_in_directory(
+                descriptors.clone(),
+                swf,
+                file.path(),
+                &output,
+                &progress,
+                opt,
+                trace_output,
+            )
+        }))
+        .unwrap_or_else(|_| Err(anyhow!("Panicked while exporting")));
+        match result {
+            Ok(true) => {
+                succeeded.fetch_add(1, Ordering::Relaxed);
+            }
+            Ok(false) => {
+                skipped.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) if opt.fail_fast => {
+                return Err(e.context(format!(
+                    "Failed to export {}",
+                    file.path().to_string_lossy()
+                )));
+            }
+            Err(e) => {
+                let message = format!("Failed to export {}: {e}", file.path().to_string_lossy());
+                if let Some(progress) = &progress {
+                    progress.println(message);
+                } else {
+                    eprintln!("{message}");
                 }
+                failed.lock().unwrap().push(file.path().to_path_buf());
             }
         }
 
         Ok(())
     })?;
 
+    let failed = failed.into_inner().unwrap();
+
     let message = if opt.frames == 1 {
         format!(
             "Saved first frame of {} files to {}",
-            files.len(),
+            succeeded.into_inner(),
             output.to_string_lossy()
         )
     } else {
         format!(
             "Saved first {} frames of {} files to {}",
             opt.frames,
-            files.len(),
+            succeeded.into_inner(),
             output.to_string_lossy()
         )
     };
+    let message = format!(
+        "{message} ({} failed, {} skipped)",
+        failed.len(),
+        skipped.into_inner()
+    );
 
     if let Some(progress) = progress {
         progress.finish_with_message(message);
@@ -381,6 +607,19 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
         println!("{message}");
     }
 
+    if !failed.is_empty() {
+        let failed: Vec<_> = failed
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        return Err(anyhow!(
+            "{} of {} files failed to export:\n{}",
+            failed.len(),
+            files.len(),
+            failed.join("\n")
+        ));
+    }
+
     Ok(())
 }
 
@@ -399,29 +638,112 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
     None
 }
 
-fn main() -> Result<()> {
-    let opt: Opt = Opt::parse();
+/// Backends to fall back to, in order, when the requested one can't be used.
+/// Only backends that exist on the current platform are listed.
+const FALLBACK_BACKENDS: &[GraphicsBackend] = &[
+    #[cfg(not(target_vendor = "apple"))]
+    GraphicsBackend::Vulkan,
+    #[cfg(target_vendor = "apple")]
+    GraphicsBackend::Metal,
+    #[cfg(windows)]
+    GraphicsBackend::Dx12,
+    GraphicsBackend::Gl,
+];
+
+fn create_descriptors(backend: GraphicsBackend, opt: &Opt) -> Result<Descriptors> {
     let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-        backends: opt.graphics.into(),
+        backends: backend.into(),
         dx12_shader_compiler: wgpu::Dx12Compiler::default(),
     });
-    let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
-        opt.graphics.into(),
-        &instance,
-        None,
-        opt.power.into(),
-        trace_path(&opt),
-    ))
+    let (adapter, device, queue) = if let Some(selector) = &opt.adapter {
+        let adapter = selector
+            .select(&instance, backend.into())
+            .map_err(|e| anyhow!(e.to_string()))?;
+        futures::executor::block_on(request_device_for_adapter(adapter, trace_path(opt)))
+    } else {
+        futures::executor::block_on(request_adapter_and_device(
+            backend.into(),
+            &instance,
+            None,
+            opt.power.into(),
+            trace_path(opt),
+        ))
+    }
     .map_err(|e| anyhow!(e.to_string()))?;
 
-    let descriptors = Arc::new(Descriptors::new(instance, adapter, device, queue));
+    Ok(Descriptors::new(instance, adapter, device, queue))
+}
+
+/// Tries each fallback backend in turn after the requested one failed with `error`.
+/// If none of them work, the returned error lists why each backend was rejected.
+fn create_fallback_descriptors(error: anyhow::Error, opt: &Opt) -> Result<Descriptors> {
+    let requested = wgpu::Backends::from(opt.graphics);
+    let mut failures = vec![(opt.graphics, error)];
+    for &backend in FALLBACK_BACKENDS {
+        if requested.contains(backend.into()) {
+            continue;
+        }
+        if !opt.silent {
+            let (previous, e) = failures.last().expect("Requested backend failed");
+            eprintln!("Unable to use {previous:?} graphics backend, trying {backend:?}: {e}");
+        }
+        match create_descriptors(backend, opt) {
+            Ok(descriptors) => return Ok(descriptors),
+            Err(e) => failures.push((backend, e)),
+        }
+    }
+
+    let reasons = failures
+        .iter()
+        .map(|(backend, e)| format!("{backend:?}: {e}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Err(anyhow!("No graphics backend could be used:\n{reasons}"))
+}
+
+fn main() -> Result<()> {
+    let opt: Opt = Opt::parse();
+
+    if opt.list_adapters {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: opt.graphics.into(),
+            dx12_shader_compiler: wgpu::Dx12Compiler::default(),
+        });
+        let adapters: Vec<_> = instance.enumerate_adapters(opt.graphics.into()).collect();
+        println!("{}", describe_adapters(&adapters));
+        return Ok(());
+    }
+
+    let descriptors = match create_descriptors(opt.graphics, &opt) {
+        Ok(descriptors) => descriptors,
+        // An adapter chosen with `--adapter` only makes sense for the requested backend,
+        // so never fall back to another one in that case.
+        Err(e) if opt.strict_graphics || opt.adapter.is_some() => return Err(e),
+        Err(e) => create_fallback_descriptors(e, &opt)?,
+    };
+    let descriptors = Arc::new(descriptors);
+
+    if !opt.silent {
+        eprintln!(
+            "Using {} ({:?})",
+            descriptors.adapter.get_info().name,
+            descriptors.adapter.get_info().backend
+        );
+    }
+
+    let trace_output = opt
+        .trace_output
+        .as_deref()
+        .map(open_trace_output)
+        .transpose()?;
 
-    if opt.swf.is_file() {
-        capture_single_swf(descriptors, &opt)?;
-    } else if !opt.swf.is_dir() {
+    let swf = opt.swf.as_deref().expect("swf is required by clap");
+    if swf.is_file() {
+        capture_single_swf(descriptors, swf, &opt, trace_output.as_ref())?;
+    } else if !swf.is_dir() {
         return Err(anyhow!("Given path is not a file or directory."));
     } else if opt.output_path.is_some() {
-        capture_multiple_swfs(descriptors, &opt)?;
+        capture_multiple_swfs(descriptors, swf, &opt, trace_output.as_ref())?;
     } else {
         return Err(anyhow!(
             "Output directory is required when exporting multiple files."
@@ -430,3 +752,35 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_background() {
+        assert_eq!("swf".parse::<Background>(), Ok(Background::Swf));
+        assert_eq!(
+            "transparent".parse::<Background>(),
+            Ok(Background::Transparent)
+        );
+        assert_eq!(
+            "#FF8000".parse::<Background>(),
+            Ok(Background::Color(Color::from_rgb(0xFF8000, 255)))
+        );
+        assert_eq!(
+            "00a0ff".parse::<Background>(),
+            Ok(Background::Color(Color::from_rgb(0x00A0FF, 255)))
+        );
+    }
+
+    #[test]
+    fn parse_invalid_background() {
+        for background in ["", "#", "#FF80", "#FF80000", "#GG8000", "#+F8000", "green"] {
+            assert!(
+                background.parse::<Background>().is_err(),
+                "{background:?} should not parse"
+            );
+        }
+    }
+}
@@ -159,6 +159,15 @@ impl WgpuRenderBackend<crate::target::TextureTarget> {
     }
 
     pub fn capture_frame(&self) -> Option<image::RgbaImage> {
+        self.capture_frame_with_alpha(false)
+    }
+
+    /// Captures the last rendered frame exactly as the GPU produced it, with premultiplied alpha.
+    pub fn capture_frame_premultiplied(&self) -> Option<image::RgbaImage> {
+        self.capture_frame_with_alpha(true)
+    }
+
+    fn capture_frame_with_alpha(&self, premultiplied: bool) -> Option<image::RgbaImage> {
         use crate::utils::buffer_to_image;
         if let Some(buffer) = &self.target.buffer {
             let (buffer, dimensions) = buffer.buffer.inner();
@@ -168,11 +177,78 @@ impl WgpuRenderBackend<crate::target::TextureTarget> {
                 dimensions,
                 None,
                 self.target.size,
+                premultiplied,
             ))
         } else {
             None
         }
     }
+
+    /// Captures a region of the last rendered frame, copying only that rectangle back from
+    /// the GPU instead of the whole target. The region is clamped to the size of the target.
+    pub fn capture_region(&self, mut region: PixelRegion) -> Option<image::RgbaImage> {
+        use crate::utils::buffer_to_image;
+        region.clamp(self.target.width(), self.target.height());
+        if region.width() == 0 || region.height() == 0 {
+            return None;
+        }
+
+        let size = wgpu::Extent3d {
+            width: region.width(),
+            height: region.height(),
+            depth_or_array_layers: 1,
+        };
+        let dimensions = BufferDimensions::new(size.width as usize, size.height as usize);
+        let buffer_label = create_debug_label!("Render target region buffer");
+        let buffer = self
+            .descriptors
+            .device
+            .create_buffer(&wgpu::BufferDescriptor {
+                label: buffer_label.as_deref(),
+                size: dimensions.size(),
+                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            });
+
+        let label = create_debug_label!("Render target region transfer encoder");
+        let mut encoder =
+            self.descriptors
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: label.as_deref(),
+                });
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &self.target.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: region.x_min,
+                    y: region.y_min,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(dimensions.padded_bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            size,
+        );
+        let index = self.descriptors.queue.submit(Some(encoder.finish()));
+
+        Some(buffer_to_image(
+            &self.descriptors.device,
+            &buffer,
+            &dimensions,
+            Some(index),
+            size,
+            false,
+        ))
+    }
 }
 
 impl<T: RenderTarget> WgpuRenderBackend<T> {
@@ -1075,6 +1151,16 @@ pub async fn request_adapter_and_device(
     Ok((adapter, device, queue))
 }
 
+/// Requests a device from an adapter that has already been chosen, such as one picked by the user
+/// instead of through `wgpu::Instance::request_adapter`.
+pub async fn request_device_for_adapter(
+    adapter: wgpu::Adapter,
+    trace_path: Option<&Path>,
+) -> Result<(wgpu::Adapter, wgpu::Device, wgpu::Queue), Error> {
+    let (device, queue) = request_device(&adapter, trace_path).await?;
+    Ok((adapter, device, queue))
+}
+
 // We try to request the highest limits we can get away with
 async fn request_device(
     adapter: &wgpu::Adapter,
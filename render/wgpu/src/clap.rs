@@ -1,3 +1,8 @@
+use crate::Error;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
 #[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
 pub enum GraphicsBackend {
     Default,
@@ -33,3 +38,127 @@ impl From<PowerPreference> for wgpu::PowerPreference {
         }
     }
 }
+
+/// Picks a specific graphics adapter, either by its index in the list given by
+/// [`describe_adapters`] or by a case-insensitive substring of its name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdapterSelector {
+    Index(usize),
+    Name(String),
+}
+
+impl FromStr for AdapterSelector {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.parse() {
+            Ok(index) => AdapterSelector::Index(index),
+            Err(_) => AdapterSelector::Name(s.to_string()),
+        })
+    }
+}
+
+impl fmt::Display for AdapterSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdapterSelector::Index(index) => write!(f, "index {index}"),
+            AdapterSelector::Name(name) => write!(f, "\"{name}\""),
+        }
+    }
+}
+
+impl AdapterSelector {
+    /// Finds the matching adapter out of every adapter available for the given backends.
+    pub fn select(
+        &self,
+        instance: &wgpu::Instance,
+        backends: wgpu::Backends,
+    ) -> Result<wgpu::Adapter, Error> {
+        let mut adapters: Vec<_> = instance.enumerate_adapters(backends).collect();
+        let position = match self {
+            AdapterSelector::Index(index) => Some(*index).filter(|index| *index < adapters.len()),
+            AdapterSelector::Name(name) => {
+                let name = name.to_lowercase();
+                adapters
+                    .iter()
+                    .position(|adapter| adapter.get_info().name.to_lowercase().contains(&name))
+            }
+        };
+
+        match position {
+            Some(position) => Ok(adapters.swap_remove(position)),
+            None => Err(format!(
+                "No graphics adapter matching {self} was found. Available adapters:\n{}",
+                describe_adapters(&adapters)
+            )
+            .into()),
+        }
+    }
+}
+
+/// Formats one line per adapter, numbered the same way as [`AdapterSelector::Index`].
+pub fn describe_adapters(adapters: &[wgpu::Adapter]) -> String {
+    if adapters.is_empty() {
+        return "(none)".to_string();
+    }
+
+    adapters
+        .iter()
+        .enumerate()
+        .map(|(index, adapter)| {
+            let info = adapter.get_info();
+            format!(
+                "{index}: {} ({:?}, {:?})",
+                info.name, info.backend, info.device_type
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_index() {
+        assert_eq!(
+            "0".parse::<AdapterSelector>(),
+            Ok(AdapterSelector::Index(0))
+        );
+        assert_eq!(
+            "12".parse::<AdapterSelector>(),
+            Ok(AdapterSelector::Index(12))
+        );
+    }
+
+    #[test]
+    fn parse_name() {
+        assert_eq!(
+            "GeForce".parse::<AdapterSelector>(),
+            Ok(AdapterSelector::Name("GeForce".to_string()))
+        );
+        assert_eq!(
+            "-1".parse::<AdapterSelector>(),
+            Ok(AdapterSelector::Name("-1".to_string()))
+        );
+        assert_eq!(
+            "".parse::<AdapterSelector>(),
+            Ok(AdapterSelector::Name("".to_string()))
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(AdapterSelector::Index(3).to_string(), "index 3");
+        assert_eq!(
+            AdapterSelector::Name("Intel".to_string()).to_string(),
+            "\"Intel\""
+        );
+    }
+
+    #[test]
+    fn describe_no_adapters() {
+        assert_eq!(describe_adapters(&[]), "(none)");
+    }
+}
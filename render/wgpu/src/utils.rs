@@ -167,6 +167,7 @@ pub fn buffer_to_image(
     dimensions: &BufferDimensions,
     index: Option<wgpu::SubmissionIndex>,
     size: wgpu::Extent3d,
+    premultiplied: bool,
 ) -> image::RgbaImage {
     capture_image(device, buffer, dimensions, index, |rgba, _buffer_width| {
         let mut bytes = Vec::with_capacity(dimensions.height * dimensions.unpadded_bytes_per_row);
@@ -177,7 +178,9 @@ pub fn buffer_to_image(
 
         // The image copied from the GPU uses premultiplied alpha, so
         // convert to straight alpha if requested by the user.
-        ruffle_render::utils::unmultiply_alpha_rgba(&mut bytes);
+        if !premultiplied {
+            ruffle_render::utils::unmultiply_alpha_rgba(&mut bytes);
+        }
 
         image::RgbaImage::from_raw(size.width, size.height, bytes)
             .expect("Retrieved texture buffer must be a valid RgbaImage")
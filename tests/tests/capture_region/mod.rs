@@ -0,0 +1,65 @@
+use crate::util::test::Test;
+use anyhow::anyhow;
+use image::imageops::crop_imm;
+use ruffle_render::bitmap::PixelRegion;
+use ruffle_render_wgpu::backend::WgpuRenderBackend;
+use ruffle_render_wgpu::target::TextureTarget;
+use std::path::Path;
+
+pub fn capture_region() -> Result<(), libtest_mimic::Failed> {
+    // Render a known movie and verify that reading back part of the frame
+    // gives the same pixels as cropping the whole frame.
+    Test::from_options_file(
+        Path::new("tests/swfs/visual/simple_shapes/overlaps/test.toml"),
+        "capture_region".to_string(),
+    )?
+    .run(
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            player.render();
+            let renderer = player
+                .renderer_mut()
+                .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+                .ok_or_else(|| anyhow!("Test needs a wgpu renderer"))?;
+
+            let full_image = renderer
+                .capture_frame()
+                .ok_or_else(|| anyhow!("Failed to capture frame"))?;
+            let (width, height) = full_image.dimensions();
+            let inside =
+                PixelRegion::for_region(width / 4, height / 4, width / 2 + 1, height / 2 + 1);
+            let overhanging = PixelRegion::for_region(width / 2, height / 2, width, height);
+
+            for region in [inside, overhanging] {
+                let mut clamped = region;
+                clamped.clamp(width, height);
+                let expected = crop_imm(
+                    &full_image,
+                    clamped.x_min,
+                    clamped.y_min,
+                    clamped.width(),
+                    clamped.height(),
+                )
+                .to_image();
+                let actual = renderer
+                    .capture_region(region)
+                    .ok_or_else(|| anyhow!("Failed to capture region {region:?}"))?;
+                if actual != expected {
+                    return Err(anyhow!(
+                        "Capture of region {region:?} doesn't match the same region of the full frame"
+                    ));
+                }
+            }
+
+            if renderer
+                .capture_region(PixelRegion::for_region(0, 0, 0, 0))
+                .is_some()
+            {
+                return Err(anyhow!("Capture of an empty region should return nothing"));
+            }
+
+            Ok(())
+        },
+    )
+}
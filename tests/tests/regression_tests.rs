@@ -2,6 +2,8 @@
 //!
 //! Trace output can be compared with correct output from the official Flash Player.
 
+#[cfg(feature = "imgtests")]
+use crate::capture_region::capture_region;
 use crate::external_interface::tests::{external_interface_avm1, external_interface_avm2};
 use crate::shared_object::{shared_object_avm1, shared_object_avm2, shared_object_self_ref_avm1};
 use anyhow::Context;
@@ -11,6 +13,8 @@ use std::panic::{catch_unwind, resume_unwind};
 use std::path::Path;
 use util::test::Test;
 
+#[cfg(feature = "imgtests")]
+mod capture_region;
 mod external_interface;
 mod shared_object;
 mod util;
@@ -113,6 +117,12 @@ fn main() {
         "external_interface_avm2",
         external_interface_avm2,
     ));
+    #[cfg(feature = "imgtests")]
+    if is_candidate(&args, "capture_region") {
+        // Only set up wgpu when this test is actually going to run.
+        let ignore = !args.list && crate::util::environment::wgpu_descriptors().is_none();
+        tests.push(Trial::test("capture_region", capture_region).with_ignored_flag(ignore));
+    }
 
     tests.sort_unstable_by(|a, b| a.name().cmp(b.name()));
 